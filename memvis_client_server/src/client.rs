@@ -1,13 +1,13 @@
 use std::{
     env,
-    io::{stdout, Read, Write},
+    io::{stdout, Write},
     net::{TcpStream, SocketAddr},
     process,
 };
 
 use crossterm::{
     execute,
-    cursor::MoveTo,
+    cursor::{MoveTo, MoveToColumn},
     event::{self, Event, KeyCode},
     terminal::{enable_raw_mode, disable_raw_mode, Clear, ClearType},
 };
@@ -18,10 +18,14 @@ mod proto {
     include!(concat!(env!("OUT_DIR"), "/memory.rs"));
 }
 
-use proto::{Command, MemoryDump};
+mod wire;
+
+use proto::{Command, MemoryDump, StructSchema, FieldSpec};
 use proto::command::CommandType;
+use proto::field_spec::{Endianness, FieldType};
+
+use wire::{MSG_COMMAND, SecureChannel, load_key, read_message, write_message};
 
-const DUMP_BUFFER_SIZE: usize = 4096;
 const REFRESH_KEY_UP: CommandType = CommandType::Up;
 const REFRESH_KEY_DOWN: CommandType = CommandType::Down;
 const INIT_COMMAND: CommandType = CommandType::Pid;
@@ -59,74 +63,67 @@ fn get_arguments() -> (SocketAddr, u32) {
     (address, pid)
 }
 
-fn send_command(stream: &mut TcpStream, command_type: CommandType, pid: u32) -> std::io::Result<()> {
-    let command = Command {
-        command_type: command_type as i32,
-        pid,
-    };
-
+// Frame and send one command, sealing it through the shared channel.
+fn send_raw(stream: &mut TcpStream, channel: &mut SecureChannel, command: Command) -> std::io::Result<()> {
     let mut buf = Vec::new();
     command.encode(&mut buf)?;
-    stream.write_all(&buf)?;
-    Ok(())
+    write_message(stream, channel, MSG_COMMAND, 0, &buf)
+}
+
+fn send_command(stream: &mut TcpStream, channel: &mut SecureChannel, command_type: CommandType, pid: u32) -> std::io::Result<()> {
+    send_raw(stream, channel, Command {
+        command_type: command_type as i32,
+        pid,
+        address: 0,
+        data: Vec::new(),
+        schema: None,
+    })
 }
 
-fn read_memory_dump(stream: &mut TcpStream) -> Option<MemoryDump> {
-    let mut buffer = vec![0; DUMP_BUFFER_SIZE];
-    let size = stream.read(&mut buffer).ok()?;
-    MemoryDump::decode(&buffer[..size]).ok()
+fn read_memory_dump(stream: &mut TcpStream, channel: &SecureChannel) -> Option<MemoryDump> {
+    let (_header, payload) = read_message(stream, channel).ok()?;
+    MemoryDump::decode(&payload[..]).ok()
 }
 
 fn handle_input(stream: &mut TcpStream, pid: u32) {
-    send_command(stream, INIT_COMMAND, pid).unwrap();
-    println_aligned!("Use ↑/↓ to navigate memory. Press 'q' to quit.\n");
+    // The client writes client→server messages and reads server→client ones.
+    let key = load_key();
+    let mut send = SecureChannel::new(key);
+    let recv = SecureChannel::new(key);
+
+    send_command(stream, &mut send, INIT_COMMAND, pid).unwrap();
+    println_aligned!("Use ↑/↓ to navigate. Press ':' for a command (goto/find/write/struct), 'q' to quit.\n");
 
     enable_raw_mode().expect("Failed to enable raw mode");
 
     loop {
         if let Event::Key(event) = event::read().unwrap() {
-            let command = match event.code {
-                KeyCode::Up => Some(REFRESH_KEY_UP),
-                KeyCode::Down => Some(REFRESH_KEY_DOWN),
+            match event.code {
+                KeyCode::Up => {
+                    send_command(stream, &mut send, REFRESH_KEY_UP, pid).unwrap();
+                    render_dumps(stream, &recv);
+                }
+                KeyCode::Down => {
+                    send_command(stream, &mut send, REFRESH_KEY_DOWN, pid).unwrap();
+                    render_dumps(stream, &recv);
+                }
+                // ':' opens a command line, mirroring the local console's REPL.
+                KeyCode::Char(':') => match read_command_line() {
+                    Some(line) => match build_command(&line, pid) {
+                        Ok(Some(cmd)) => {
+                            send_raw(stream, &mut send, cmd).unwrap();
+                            render_dumps(stream, &recv);
+                        }
+                        Ok(None) => {}
+                        Err(msg) => println_aligned!("{}", msg),
+                    },
+                    None => {}
+                },
                 KeyCode::Char('q') | KeyCode::Char('Q') => {
                     println_aligned!("Exiting...");
                     break;
                 }
-                _ => None,
-            };
-
-            if let Some(cmd_type) = command {
-                send_command(stream, cmd_type, pid).unwrap();
-
-                execute!(stdout(), Clear(ClearType::All), MoveTo(0, 0)).unwrap();
-
-                if let Some(dump) = read_memory_dump(stream) {
-                    if dump.status.contains("Insufficient permission") {
-                        println_aligned!("{}", dump.status);
-                        println_aligned!("Client exiting due to insufficient rights.");
-                        disable_raw_mode().expect("Failed to disable raw mode");
-                        println_aligned!("");
-                        process::exit(1);
-                    }
-
-                    println_aligned!("{}", dump.status);
-
-                    println_aligned!(
-                        "Region [{}] 0x{:X} - 0x{:X} | Name: {}",
-                        dump.region_index,
-                        dump.region_start,
-                        dump.region_end,
-                        dump.region_name
-                    );
-
-                    for line in dump.lines.iter() {
-                        let clean = line.trim_end_matches(&['\r', '\n'][..]);
-                        println_aligned!("{}", clean)
-                    }
-
-                } else {
-                    println_aligned!("Failed to decode memory dump.");
-                }
+                _ => {}
             }
         }
     }
@@ -135,6 +132,218 @@ fn handle_input(stream: &mut TcpStream, pid: u32) {
     println_aligned!("");
 }
 
+// Read and render every frame of one server response, progressively so large
+// dumps appear as they land rather than all at once.
+fn render_dumps(stream: &mut TcpStream, recv: &SecureChannel) {
+    execute!(stdout(), Clear(ClearType::All), MoveTo(0, 0)).unwrap();
+
+    loop {
+        let Some(dump) = read_memory_dump(stream, recv) else {
+            println_aligned!("Failed to decode memory dump.");
+            break;
+        };
+
+        if dump.status.contains("Insufficient permission") {
+            println_aligned!("{}", dump.status);
+            println_aligned!("Client exiting due to insufficient rights.");
+            disable_raw_mode().expect("Failed to disable raw mode");
+            println_aligned!("");
+            process::exit(1);
+        }
+
+        if dump.index == 0 {
+            println_aligned!("{}", dump.status);
+            println_aligned!(
+                "Region [{}] 0x{:X} - 0x{:X} | Name: {}",
+                dump.region_index,
+                dump.region_start,
+                dump.region_end,
+                dump.region_name
+            );
+            if let Some(regs) = &dump.registers {
+                println_aligned!("Registers: rip=0x{:X} rsp=0x{:X}", regs.rip, regs.rsp);
+            }
+        }
+
+        for line in dump.lines.iter() {
+            let clean = line.trim_end_matches(&['\r', '\n'][..]);
+            println_aligned!("{}", clean)
+        }
+
+        // When a struct overlay is active the server appends a field-by-field
+        // rendering; show it below the hex grid.
+        if !dump.decoded.is_empty() {
+            println_aligned!("── struct overlay ──");
+            for line in dump.decoded.iter() {
+                println_aligned!("{}", line.trim_end_matches(&['\r', '\n'][..]));
+            }
+        }
+
+        let total = dump.total.max(1);
+        if dump.index + 1 >= total {
+            break;
+        }
+    }
+}
+
+// Read a `:`-prefixed command line in raw mode, echoing as it is typed. Returns
+// the typed line on Enter, or `None` if the user cancels with Esc.
+fn read_command_line() -> Option<String> {
+    let mut buf = String::new();
+    redraw_prompt(&buf);
+    loop {
+        if let Ok(Event::Key(event)) = event::read() {
+            match event.code {
+                KeyCode::Enter => return Some(buf),
+                KeyCode::Esc => return None,
+                KeyCode::Backspace => {
+                    buf.pop();
+                }
+                KeyCode::Char(c) => buf.push(c),
+                _ => {}
+            }
+            redraw_prompt(&buf);
+        }
+    }
+}
+
+// Repaint the command prompt line with its current contents.
+fn redraw_prompt(buf: &str) {
+    execute!(stdout(), MoveToColumn(0), Clear(ClearType::CurrentLine)).unwrap();
+    let mut out = stdout();
+    write!(out, ":{}", buf).unwrap();
+    out.flush().unwrap();
+}
+
+// Parse a typed command line into a `Command` to send, a status string to show
+// on a parse error, or `None` when the line is blank.
+fn build_command(line: &str, pid: u32) -> Result<Option<Command>, String> {
+    let mut tokens = line.split_whitespace();
+    match tokens.next() {
+        None => Ok(None),
+        Some("goto") => {
+            let address = tokens
+                .next()
+                .and_then(parse_hex_addr)
+                .ok_or("goto: expected a hex address")?;
+            Ok(Some(Command {
+                command_type: CommandType::Goto as i32,
+                pid,
+                address,
+                data: Vec::new(),
+                schema: None,
+            }))
+        }
+        Some("find") => {
+            let data = parse_hex_bytes(tokens.next().unwrap_or(""))
+                .filter(|b| !b.is_empty())
+                .ok_or("find: expected hex bytes")?;
+            Ok(Some(Command {
+                command_type: CommandType::Find as i32,
+                pid,
+                address: 0,
+                data,
+                schema: None,
+            }))
+        }
+        Some("write") => {
+            let address = tokens
+                .next()
+                .and_then(parse_hex_addr)
+                .ok_or("write: expected a hex address")?;
+            let data = parse_hex_bytes(tokens.next().unwrap_or(""))
+                .filter(|b| !b.is_empty())
+                .ok_or("write: expected hex bytes")?;
+            Ok(Some(Command {
+                command_type: CommandType::Write as i32,
+                pid,
+                address,
+                data,
+                schema: None,
+            }))
+        }
+        // `struct name:type[:be|le],...` lays an overlay over the current
+        // window; `struct` alone clears it. The schema rides on an Unknown
+        // command so the server just re-renders the current window with it.
+        Some("struct") => {
+            let spec: String = tokens.collect::<Vec<_>>().join(" ");
+            let schema = parse_schema(&spec)?;
+            Ok(Some(Command {
+                command_type: CommandType::Unknown as i32,
+                pid,
+                address: 0,
+                data: Vec::new(),
+                schema: Some(schema),
+            }))
+        }
+        Some(other) => Err(format!("unknown command: {}", other)),
+    }
+}
+
+/// Parse a struct overlay spec — comma-separated `name:type[:be|le]` fields —
+/// into a [`StructSchema`]. An empty spec yields an empty schema, which clears
+/// any active overlay on the server.
+fn parse_schema(spec: &str) -> Result<StructSchema, String> {
+    let mut fields = Vec::new();
+    for field in spec.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        let mut parts = field.split(':');
+        let name = parts.next().unwrap_or("").trim().to_string();
+        if name.is_empty() {
+            return Err(format!("struct: missing field name in '{}'", field));
+        }
+        let ty = match parts.next() {
+            Some(t) => parse_field_type(t.trim())?,
+            None => return Err(format!("struct: field '{}' needs a type", name)),
+        };
+        let endianness = match parts.next().map(str::trim) {
+            None | Some("le") => Endianness::Little,
+            Some("be") => Endianness::Big,
+            Some(other) => return Err(format!("struct: unknown endianness '{}'", other)),
+        };
+        fields.push(FieldSpec {
+            name,
+            r#type: ty as i32,
+            endianness: endianness as i32,
+        });
+    }
+    Ok(StructSchema { fields })
+}
+
+/// Map a type keyword to its [`FieldType`].
+fn parse_field_type(token: &str) -> Result<FieldType, String> {
+    match token {
+        "u8" => Ok(FieldType::U8),
+        "u16" => Ok(FieldType::U16),
+        "u32" => Ok(FieldType::U32),
+        "u64" => Ok(FieldType::U64),
+        "i8" => Ok(FieldType::I8),
+        "i16" => Ok(FieldType::I16),
+        "i32" => Ok(FieldType::I32),
+        "i64" => Ok(FieldType::I64),
+        "f32" => Ok(FieldType::F32),
+        "f64" => Ok(FieldType::F64),
+        "ptr" | "pointer" => Ok(FieldType::Pointer),
+        other => Err(format!("struct: unknown field type '{}'", other)),
+    }
+}
+
+/// Parse an absolute address written in hex, with or without a `0x` prefix.
+fn parse_hex_addr(token: &str) -> Option<u64> {
+    u64::from_str_radix(token.trim_start_matches("0x"), 16).ok()
+}
+
+/// Parse a run of hex bytes (e.g. `deadbeef` or `de ad be ef`) into a pattern.
+fn parse_hex_bytes(token: &str) -> Option<Vec<u8>> {
+    let digits: String = token.chars().filter(|c| !c.is_whitespace()).collect();
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect()
+}
+
 fn main() {
     let (address, pid) = get_arguments();
 
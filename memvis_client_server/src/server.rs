@@ -1,7 +1,7 @@
 use std::{
     env,
-    fs::File,
-    io::{BufRead, BufReader, Read, Seek, Write},
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Read, Seek, SeekFrom, Write},
     net::{TcpListener, TcpStream, SocketAddr},
     process,
 };
@@ -12,33 +12,55 @@ mod proto {
     include!(concat!(env!("OUT_DIR"), "/memory.rs"));
 }
 
-use proto::{Command, MemoryDump};
+mod wire;
+
+use proto::{Command, MemoryDump, Registers, StructSchema};
 use proto::command::CommandType;
+use proto::field_spec::{Endianness, FieldType};
+
+use wire::{MSG_COMMAND, MSG_MEMORY_DUMP, FLAG_ERROR, SecureChannel, load_key, read_message, write_message};
 
 const CHUNK_SIZE: usize = 128;             // Number of bytes sent per memory response
 const BYTES_PER_LINE: usize = 16;          // Number of bytes displayed per formatted line
 const HEX_DISPLAY_WIDTH: usize = 3 * BYTES_PER_LINE; // Width of hex section: 2 digits + space
 const ASCII_WIDTH: usize = BYTES_PER_LINE; // Width of ASCII section
-const MAX_BUFFER_SIZE: usize = 1024;       // Incoming buffer size per message
+const FRAME_LINES: usize = 2;              // Formatted lines emitted per streamed frame
 
 struct ClientState {
     pid: u32,
     region_index: usize,
     offset_within_region: usize,
     mem_regions: Vec<(usize, usize, String)>,
+    // Active struct overlay, applied to every window until the client clears it
+    // by sending a schema with no fields.
+    schema: Option<StructSchema>,
 }
 
-// 🎯 Parse command-line arguments
-fn get_server_address() -> SocketAddr {
+// 🎯 Parse command-line arguments. Returns the bind address and whether memory
+// writes are permitted (opt-in via `--allow-write`).
+fn get_server_config() -> (SocketAddr, bool) {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        eprintln!("❌ Usage: {} <client_address>:<port>", args[0]);
-        process::exit(1);
+    let mut address: Option<SocketAddr> = None;
+    let mut allow_write = false;
+
+    for arg in &args[1..] {
+        if arg == "--allow-write" {
+            allow_write = true;
+        } else {
+            address = Some(arg.parse().unwrap_or_else(|e| {
+                eprintln!("❌ Invalid address '{}': {}", arg, e);
+                process::exit(1);
+            }));
+        }
+    }
+
+    match address {
+        Some(addr) => (addr, allow_write),
+        None => {
+            eprintln!("❌ Usage: {} <client_address>:<port> [--allow-write]", args[0]);
+            process::exit(1);
+        }
     }
-    args[1].parse().unwrap_or_else(|e| {
-        eprintln!("❌ Invalid address '{}': {}", args[1], e);
-        process::exit(1);
-    })
 }
 
 // 📍 Parse /proc/<pid>/maps for memory regions
@@ -87,6 +109,71 @@ fn format_memory_chunk(address: usize, data: &[u8]) -> Vec<String> {
         .collect()
 }
 
+// The byte width of a field type, used to advance the overlay cursor.
+fn field_width(ty: FieldType) -> usize {
+    match ty {
+        FieldType::U8 | FieldType::I8 => 1,
+        FieldType::U16 | FieldType::I16 => 2,
+        FieldType::U32 | FieldType::I32 | FieldType::F32 => 4,
+        FieldType::U64 | FieldType::I64 | FieldType::F64 | FieldType::Pointer => 8,
+    }
+}
+
+// Render one field's bytes as its declared type, honouring endianness. Unsigned
+// integers are shown in both decimal and hex, signed integers in decimal, floats
+// in their natural form, and pointers as a hex address the UI can follow with
+// `goto`.
+fn format_field(ty: FieldType, endian: Endianness, bytes: &[u8]) -> String {
+    let mut ordered = bytes.to_vec();
+    if matches!(endian, Endianness::Big) {
+        ordered.reverse();
+    }
+    let mut buf = [0u8; 8];
+    buf[..ordered.len()].copy_from_slice(&ordered);
+    let raw = u64::from_le_bytes(buf);
+
+    match ty {
+        FieldType::U8 => format!("{} (0x{:02X})", raw as u8, raw as u8),
+        FieldType::U16 => format!("{} (0x{:04X})", raw as u16, raw as u16),
+        FieldType::U32 => format!("{} (0x{:08X})", raw as u32, raw as u32),
+        FieldType::U64 => format!("{} (0x{:016X})", raw, raw),
+        FieldType::I8 => format!("{}", raw as u8 as i8),
+        FieldType::I16 => format!("{}", raw as u16 as i16),
+        FieldType::I32 => format!("{}", raw as u32 as i32),
+        FieldType::I64 => format!("{}", raw as i64),
+        FieldType::F32 => format!("{}", f32::from_bits(raw as u32)),
+        FieldType::F64 => format!("{}", f64::from_bits(raw)),
+        FieldType::Pointer => format!("-> 0x{:016X}", raw),
+    }
+}
+
+// Lay `schema` over `data` starting at `base`, emitting one line per field with
+// its address, name, type and decoded value. The first field that runs past the
+// end of the window is reported as truncated and decoding stops there; the
+// fields after it are not listed.
+fn decode_struct(base: usize, data: &[u8], schema: &StructSchema) -> Vec<String> {
+    let mut cursor = 0usize;
+    let mut lines = Vec::with_capacity(schema.fields.len());
+
+    for field in &schema.fields {
+        let ty = FieldType::try_from(field.r#type).unwrap_or(FieldType::U8);
+        let endian = Endianness::try_from(field.endianness).unwrap_or(Endianness::Little);
+        let width = field_width(ty);
+        let addr = base + cursor;
+
+        if cursor + width > data.len() {
+            lines.push(format!("0x{:08X} | {}: <truncated>", addr, field.name));
+            break;
+        }
+
+        let value = format_field(ty, endian, &data[cursor..cursor + width]);
+        lines.push(format!("0x{:08X} | {}: {}", addr, field.name, value));
+        cursor += width;
+    }
+
+    lines
+}
+
 // 🧠 Read memory from /proc/<pid>/mem
 fn read_memory(pid: u32, address: usize, size: usize) -> Result<Vec<u8>, std::io::Error> {
     let path = format!("/proc/{}/mem", pid);
@@ -98,6 +185,130 @@ fn read_memory(pid: u32, address: usize, size: usize) -> Result<Vec<u8>, std::io
 }
 
 
+// ✍️ Poke bytes into the target via /proc/<pid>/mem.
+fn write_memory(pid: u32, address: usize, data: &[u8]) -> Result<(), std::io::Error> {
+    let path = format!("/proc/{}/mem", pid);
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    file.seek(SeekFrom::Start(address as u64))?;
+    file.write_all(data)?;
+    Ok(())
+}
+
+// 🔒 Whether `address` falls inside a writable region according to its maps
+// permission string.
+fn is_writable(pid: u32, address: usize) -> bool {
+    let path = format!("/proc/{}/maps", pid);
+    let reader = match File::open(path) {
+        Ok(f) => BufReader::new(f),
+        Err(_) => return false,
+    };
+
+    for line in reader.lines().map_while(Result::ok) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let (Some(range), Some(perms)) = (parts.first(), parts.get(1)) else {
+            continue;
+        };
+        let mut bounds = range.split('-');
+        let start = bounds.next().and_then(|s| usize::from_str_radix(s, 16).ok());
+        let end = bounds.next().and_then(|s| usize::from_str_radix(s, 16).ok());
+        if let (Some(start), Some(end)) = (start, end) {
+            if start <= address && address < end {
+                return perms.contains('w');
+            }
+        }
+    }
+    false
+}
+
+// Position the client state at the region containing `address`, if any.
+fn locate(state: &mut ClientState, address: usize) -> bool {
+    for (index, (start, end, _)) in state.mem_regions.iter().enumerate() {
+        if *start <= address && address < *end {
+            state.region_index = index;
+            state.offset_within_region = address - start;
+            return true;
+        }
+    }
+    false
+}
+
+// 🔎 Scan forward from the current position for `pattern`, crossing region
+// boundaries. Unreadable regions are skipped; each region is read in CHUNK_SIZE
+// strides that overlap by `pattern.len() - 1` so matches straddling a chunk
+// boundary are still found. Returns the (region index, offset) of the first hit.
+fn find_pattern_in_process(state: &ClientState, pattern: &[u8]) -> Option<(usize, usize)> {
+    let plen = pattern.len();
+    for index in state.region_index..state.mem_regions.len() {
+        let (start, end, _) = state.mem_regions[index];
+        let size = end - start;
+        let mut pos = if index == state.region_index {
+            state.offset_within_region
+        } else {
+            0
+        };
+
+        while pos < size {
+            let read_len = CHUNK_SIZE.min(size - pos);
+            if read_len < plen {
+                break;
+            }
+            match read_memory(state.pid, start + pos, read_len) {
+                Ok(data) => {
+                    if let Some(hit) = data.windows(plen).position(|w| w == pattern) {
+                        return Some((index, pos + hit));
+                    }
+                }
+                Err(_) => break, // Unreadable: move on to the next region.
+            }
+            pos += read_len - (plen - 1); // Overlap so boundary-straddling matches are caught.
+        }
+    }
+    None
+}
+
+// Send a standalone status/error dump, optionally flagged as an error.
+fn send_status(stream: &mut TcpStream, channel: &mut SecureChannel, status: String, flags: u16) -> std::io::Result<()> {
+    let dump = MemoryDump {
+        status,
+        region_name: String::new(),
+        region_index: 0,
+        region_start: 0,
+        region_end: 0,
+        lines: vec![],
+        index: 0,
+        total: 1,
+        registers: None,
+        decoded: vec![],
+    };
+    let mut buf = Vec::new();
+    dump.encode(&mut buf)?;
+    write_message(stream, channel, MSG_MEMORY_DUMP, flags, &buf)
+}
+
+// 🧮 Read the target's stack pointer and program counter from
+// /proc/<pid>/syscall (the last two hex tokens), filling what is available.
+fn read_registers(pid: u32) -> Option<Registers> {
+    let path = format!("/proc/{}/syscall", pid);
+    let mut contents = String::new();
+    File::open(path).ok()?.read_to_string(&mut contents).ok()?;
+
+    let hex: Vec<u64> = contents
+        .split_whitespace()
+        .filter_map(|s| s.strip_prefix("0x"))
+        .filter_map(|s| u64::from_str_radix(s, 16).ok())
+        .collect();
+
+    if hex.len() < 2 {
+        return None;
+    }
+
+    Some(Registers {
+        rsp: hex[hex.len() - 2],
+        rip: hex[hex.len() - 1],
+        ..Default::default()
+    })
+}
+
 // ↕️ Update client state based on command
 fn process_command(cmd: i32, state: &mut ClientState) {
     match CommandType::try_from(cmd) {
@@ -126,7 +337,7 @@ fn process_command(cmd: i32, state: &mut ClientState) {
 }
 
 // 📦 Build and send memory dump as Protobuf
-fn send_memory_dump(state: &ClientState, stream: &mut TcpStream) -> std::io::Result<()> {
+fn send_memory_dump(state: &ClientState, stream: &mut TcpStream, channel: &mut SecureChannel) -> std::io::Result<()> {
     let (start, end, name) = &state.mem_regions[state.region_index];
     let address = start + state.offset_within_region;
 
@@ -136,18 +347,46 @@ fn send_memory_dump(state: &ClientState, stream: &mut TcpStream) -> std::io::Res
             let progress = address - start;
             let percent = ((progress as f64 / region_size as f64) * 100.0).round();
 
-            let dump = MemoryDump {
-                status: format!("Progress: {:.0}% | Offset: {} / {} bytes", percent, progress, region_size),
-                region_name: name.clone(),
-                region_index: state.region_index as u32,
-                region_start: *start as u64,
-                region_end: *end as u64,
-                lines: format_memory_chunk(address, &data),
+            // Stream the window as several frames so the client renders it
+            // progressively. Every frame carries the region bounds and its slice
+            // of the formatted lines; only the first also carries the status,
+            // region name, register snapshot and any struct overlay.
+            let lines = format_memory_chunk(address, &data);
+            let decoded = match &state.schema {
+                Some(schema) => decode_struct(address, &data, schema),
+                None => vec![],
             };
 
-            let mut buf = Vec::new();
-            dump.encode(&mut buf)?;
-            stream.write_all(&buf)?;
+            let frames: Vec<&[String]> = if lines.is_empty() {
+                vec![&[]]
+            } else {
+                lines.chunks(FRAME_LINES).collect()
+            };
+            let total = frames.len() as u32;
+
+            for (i, frame) in frames.iter().enumerate() {
+                let first = i == 0;
+                let dump = MemoryDump {
+                    status: if first {
+                        format!("Progress: {:.0}% | Offset: {} / {} bytes", percent, progress, region_size)
+                    } else {
+                        String::new()
+                    },
+                    region_name: if first { name.clone() } else { String::new() },
+                    region_index: state.region_index as u32,
+                    region_start: *start as u64,
+                    region_end: *end as u64,
+                    lines: frame.to_vec(),
+                    index: i as u32,
+                    total,
+                    registers: if first { read_registers(state.pid) } else { None },
+                    decoded: if first { decoded.clone() } else { vec![] },
+                };
+
+                let mut buf = Vec::new();
+                dump.encode(&mut buf)?;
+                write_message(stream, channel, MSG_MEMORY_DUMP, 0, &buf)?;
+            }
         }
         Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
             let error_msg = format!("🚫 Insufficient permission to read memory of PID {}. Run with sudo or check access rights.", state.pid);
@@ -158,10 +397,14 @@ fn send_memory_dump(state: &ClientState, stream: &mut TcpStream) -> std::io::Res
                 region_start: 0,
                 region_end: 0,
                 lines: vec![],
+                index: 0,
+                total: 1,
+                registers: None,
+                decoded: vec![],
             };
             let mut buf = Vec::new();
             dump.encode(&mut buf)?;
-            stream.write_all(&buf)?;
+            write_message(stream, channel, MSG_MEMORY_DUMP, FLAG_ERROR, &buf)?;
             eprintln!("{}", error_msg);      // original still usable
         }
         Err(e) => {
@@ -174,16 +417,21 @@ fn send_memory_dump(state: &ClientState, stream: &mut TcpStream) -> std::io::Res
 }
 
 // 🔄 Handle one client session
-fn handle_client(mut stream: TcpStream) {
-    let mut buffer = [0; MAX_BUFFER_SIZE];
+fn handle_client(mut stream: TcpStream, allow_write: bool) {
     let mut state: Option<ClientState> = None;
 
-    while let Ok(n) = stream.read(&mut buffer) {
-        if n == 0 {
-            break;
+    // The server reads client→server messages and writes server→client ones.
+    let key = load_key();
+    let recv = SecureChannel::new(key);
+    let mut send = SecureChannel::new(key);
+
+    while let Ok((header, payload)) = read_message(&mut stream, &recv) {
+        if header.message_id != MSG_COMMAND {
+            eprintln!("Unexpected message id: {}", header.message_id);
+            continue;
         }
 
-        let cmd = match Command::decode(&buffer[..n]) {
+        let cmd = match Command::decode(&payload[..]) {
             Ok(c) => c,
             Err(e) => {
                 eprintln!("Failed to decode command: {}", e);
@@ -191,6 +439,12 @@ fn handle_client(mut stream: TcpStream) {
             }
         };
 
+        // A struct overlay may ride along with any command; an empty field list
+        // clears it so the window reverts to the raw hex grid.
+        if let (Some(s), Some(schema)) = (state.as_mut(), cmd.schema.clone()) {
+            s.schema = if schema.fields.is_empty() { None } else { Some(schema) };
+        }
+
         match CommandType::try_from(cmd.command_type) {
             Ok(CommandType::Pid) if state.is_none() => {
                 let regions = parse_maps(cmd.pid);
@@ -203,12 +457,65 @@ fn handle_client(mut stream: TcpStream) {
                     region_index: 0,
                     offset_within_region: 0,
                     mem_regions: regions,
+                    schema: None,
                 });
             }
+            Ok(CommandType::Write) if state.is_some() => {
+                let s = state.as_mut().unwrap();
+                let address = cmd.address as usize;
+
+                if !allow_write {
+                    let _ = send_status(&mut stream, &mut send,
+                        "🚫 Writes are disabled. Restart the server with --allow-write.".to_string(),
+                        FLAG_ERROR);
+                } else if !is_writable(s.pid, address) {
+                    let _ = send_status(&mut stream, &mut send,
+                        format!("🚫 Region containing {:#018x} is not writable.", address),
+                        FLAG_ERROR);
+                } else {
+                    match write_memory(s.pid, address, &cmd.data) {
+                        // Re-position on the written address and echo the window back.
+                        Ok(()) if locate(s, address) => {
+                            let _ = send_memory_dump(s, &mut stream, &mut send);
+                        }
+                        Ok(()) => {
+                            let _ = send_status(&mut stream, &mut send,
+                                format!("Wrote {} bytes at {:#018x}.", cmd.data.len(), address), 0);
+                        }
+                        Err(e) => {
+                            let _ = send_status(&mut stream, &mut send,
+                                format!("🚫 Failed to write at {:#018x}: {}", address, e),
+                                FLAG_ERROR);
+                        }
+                    }
+                }
+            }
+            Ok(CommandType::Goto) if state.is_some() => {
+                let s = state.as_mut().unwrap();
+                let address = cmd.address as usize;
+                if locate(s, address) {
+                    let _ = send_memory_dump(s, &mut stream, &mut send);
+                } else {
+                    let _ = send_status(&mut stream, &mut send,
+                        format!("goto: {:#018x} is not in any region.", address), FLAG_ERROR);
+                }
+            }
+            Ok(CommandType::Find) if state.is_some() => {
+                let s = state.as_mut().unwrap();
+                if cmd.data.is_empty() {
+                    let _ = send_status(&mut stream, &mut send, "find: empty pattern.".to_string(), FLAG_ERROR);
+                } else if let Some((index, offset)) = find_pattern_in_process(s, &cmd.data) {
+                    s.region_index = index;
+                    s.offset_within_region = offset;
+                    let _ = send_memory_dump(s, &mut stream, &mut send);
+                } else {
+                    let _ = send_status(&mut stream, &mut send, "find: pattern not found.".to_string(), FLAG_ERROR);
+                }
+            }
             Ok(_) if state.is_some() => {
                 let s = state.as_mut().unwrap();
                 process_command(cmd.command_type, s);
-                let _ = send_memory_dump(s, &mut stream);
+                let _ = send_memory_dump(s, &mut stream, &mut send);
             }
             Err(_) => {
                 eprintln!("Unknown command type: {}", cmd.command_type);
@@ -221,14 +528,80 @@ fn handle_client(mut stream: TcpStream) {
 
 // 🚀 Entry point
 fn main() {
-    let address = get_server_address();
+    let (address, allow_write) = get_server_config();
     let listener = TcpListener::bind(address).expect("Could not bind");
-    println!("🚀 Server listening on {}", address);
+    println!("🚀 Server listening on {}{}", address, if allow_write { " (writes enabled)" } else { "" });
 
     for stream in listener.incoming() {
         match stream {
-            Ok(s) => handle_client(s),
+            Ok(s) => handle_client(s, allow_write),
             Err(e) => eprintln!("🔴 Connection error: {}", e),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto::FieldSpec;
+
+    fn field(name: &str, ty: FieldType, endian: Endianness) -> FieldSpec {
+        FieldSpec {
+            name: name.to_string(),
+            r#type: ty as i32,
+            endianness: endian as i32,
+        }
+    }
+
+    #[test]
+    fn field_widths_match_types() {
+        assert_eq!(field_width(FieldType::U8), 1);
+        assert_eq!(field_width(FieldType::I16), 2);
+        assert_eq!(field_width(FieldType::U32), 4);
+        assert_eq!(field_width(FieldType::F32), 4);
+        assert_eq!(field_width(FieldType::U64), 8);
+        assert_eq!(field_width(FieldType::Pointer), 8);
+    }
+
+    #[test]
+    fn format_field_honours_endianness() {
+        let le = format_field(FieldType::U32, Endianness::Little, &[0x78, 0x56, 0x34, 0x12]);
+        let be = format_field(FieldType::U32, Endianness::Big, &[0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(le, "305419896 (0x12345678)");
+        assert_eq!(be, "305419896 (0x12345678)");
+    }
+
+    #[test]
+    fn format_field_signed_and_pointer() {
+        assert_eq!(format_field(FieldType::I8, Endianness::Little, &[0xFF]), "-1");
+        assert_eq!(
+            format_field(FieldType::Pointer, Endianness::Little, &[0x00; 8]),
+            "-> 0x0000000000000000"
+        );
+    }
+
+    #[test]
+    fn decode_struct_advances_cursor_by_width() {
+        let schema = StructSchema {
+            fields: vec![
+                field("a", FieldType::U32, Endianness::Little),
+                field("b", FieldType::U8, Endianness::Little),
+            ],
+        };
+        let lines = decode_struct(0x1000, &[0u8; 5], &schema);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("0x00001000 | a:"));
+        // The second field sits one u32 (4 bytes) past the base.
+        assert!(lines[1].starts_with("0x00001004 | b:"));
+    }
+
+    #[test]
+    fn decode_struct_flags_truncated_field() {
+        let schema = StructSchema {
+            fields: vec![field("wide", FieldType::U64, Endianness::Little)],
+        };
+        let lines = decode_struct(0x2000, &[0u8; 4], &schema);
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("<truncated>"));
+    }
+}
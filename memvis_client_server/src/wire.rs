@@ -0,0 +1,169 @@
+//! Shared wire protocol: the fixed framing header and the optionally-encrypted
+//! channel used by both the client and the server binaries. Keeping it in one
+//! place means the framing and crypto have a single source of truth.
+//!
+//! Each binary `mod`-includes this file and pulls in only the items it needs,
+//! so allow the unused remainder per compilation unit.
+#![allow(dead_code)]
+
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+use chacha20poly1305::aead::Aead;
+use chacha20poly1305::{XChaCha20Poly1305, Key, KeyInit, XNonce};
+
+pub const HEADER_LEN: usize = 8;           // message_id (2) + message_size (4) + flags (2)
+pub const MSG_COMMAND: u16 = 1;            // Payload is a `Command`
+pub const MSG_MEMORY_DUMP: u16 = 2;        // Payload is a `MemoryDump`
+pub const FLAG_ERROR: u16 = 1;             // In-band error signal
+
+// Length of an XChaCha20-Poly1305 nonce. At 192 bits it is safe to pick at
+// random for every message — the birthday bound is ~2^96 messages — so no
+// per-connection counter or key derivation is needed to avoid reuse.
+const NONCE_LEN: usize = 24;
+
+// A fixed framing header written before every payload so the protocol is
+// robust to TCP segmentation and payloads larger than any single read.
+pub struct Header {
+    pub message_id: u16,
+    pub message_size: u32,
+    pub flags: u16,
+}
+
+impl Header {
+    fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0u8; HEADER_LEN];
+        bytes[0..2].copy_from_slice(&self.message_id.to_be_bytes());
+        bytes[2..6].copy_from_slice(&self.message_size.to_be_bytes());
+        bytes[6..8].copy_from_slice(&self.flags.to_be_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; HEADER_LEN]) -> Self {
+        Header {
+            message_id: u16::from_be_bytes([bytes[0], bytes[1]]),
+            message_size: u32::from_be_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]),
+            flags: u16::from_be_bytes([bytes[6], bytes[7]]),
+        }
+    }
+}
+
+// Draw a fresh nonce from the kernel CSPRNG. A nonce that silently defaulted to
+// zero on failure would guarantee catastrophic reuse, so a CSPRNG that cannot
+// be read is a hard error rather than a fallback.
+fn random_nonce() -> [u8; NONCE_LEN] {
+    let mut nonce = [0u8; NONCE_LEN];
+    File::open("/dev/urandom")
+        .and_then(|mut f| f.read_exact(&mut nonce))
+        .expect("CSPRNG unavailable: cannot read /dev/urandom for a nonce");
+    nonce
+}
+
+// An optionally-encrypted channel. When a pre-shared key is configured every
+// payload is sealed with XChaCha20-Poly1305 under a fresh random nonce;
+// otherwise payloads pass in clear.
+pub struct SecureChannel {
+    cipher: Option<XChaCha20Poly1305>,
+}
+
+impl SecureChannel {
+    pub fn new(key: Option<[u8; 32]>) -> Self {
+        Self {
+            cipher: key.map(|k| XChaCha20Poly1305::new(Key::from_slice(&k))),
+        }
+    }
+
+    // Produce `nonce || ciphertext || tag`, or the plaintext when no key is set.
+    // The full random nonce travels on the wire, so the peer needs no prior
+    // knowledge of it to decrypt.
+    fn seal(&mut self, plain: &[u8]) -> Vec<u8> {
+        match &self.cipher {
+            Some(cipher) => {
+                let nonce = random_nonce();
+                let ciphertext = cipher
+                    .encrypt(XNonce::from_slice(&nonce), plain)
+                    .expect("XChaCha20-Poly1305 encryption failed");
+
+                let mut out = Vec::with_capacity(nonce.len() + ciphertext.len());
+                out.extend_from_slice(&nonce);
+                out.extend_from_slice(&ciphertext);
+                out
+            }
+            None => plain.to_vec(),
+        }
+    }
+
+    // Verify and decrypt a sealed blob, or pass the plaintext through. Returns
+    // `None` on a tag mismatch so the caller can drop the connection.
+    fn open(&self, blob: &[u8]) -> Option<Vec<u8>> {
+        match &self.cipher {
+            Some(cipher) => {
+                if blob.len() < NONCE_LEN {
+                    return None;
+                }
+                let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+                cipher.decrypt(XNonce::from_slice(nonce), ciphertext).ok()
+            }
+            None => Some(blob.to_vec()),
+        }
+    }
+}
+
+// Load the 32-byte pre-shared key from MEMVIS_PSK, accepting either 64 hex
+// characters or a 32-byte raw string. Returns `None` when unset (clear transport).
+pub fn load_key() -> Option<[u8; 32]> {
+    let value = env::var("MEMVIS_PSK").ok()?;
+    let bytes = if value.len() == 64 {
+        (0..64)
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .ok()?
+    } else {
+        value.into_bytes()
+    };
+
+    let mut key = [0u8; 32];
+    if bytes.len() != 32 {
+        eprintln!("❌ MEMVIS_PSK must be 32 bytes (or 64 hex chars)");
+        return None;
+    }
+    key.copy_from_slice(&bytes);
+    Some(key)
+}
+
+// Read exactly one framed message: the fixed header, then `message_size` bytes,
+// then verify/decrypt the payload.
+pub fn read_message(stream: &mut TcpStream, channel: &SecureChannel) -> std::io::Result<(Header, Vec<u8>)> {
+    let mut header_buf = [0u8; HEADER_LEN];
+    stream.read_exact(&mut header_buf)?;
+    let header = Header::from_bytes(&header_buf);
+    let mut blob = vec![0u8; header.message_size as usize];
+    stream.read_exact(&mut blob)?;
+
+    let payload = channel.open(&blob).ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "authentication tag mismatch")
+    })?;
+    Ok((header, payload))
+}
+
+// Write a single framed message: seal the payload, then the header, then the blob.
+pub fn write_message(
+    stream: &mut TcpStream,
+    channel: &mut SecureChannel,
+    message_id: u16,
+    flags: u16,
+    payload: &[u8],
+) -> std::io::Result<()> {
+    let blob = channel.seal(payload);
+    let header = Header {
+        message_id,
+        message_size: blob.len() as u32,
+        flags,
+    };
+    stream.write_all(&header.to_bytes())?;
+    stream.write_all(&blob)?;
+    Ok(())
+}
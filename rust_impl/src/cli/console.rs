@@ -6,8 +6,16 @@ use termion::event::Key;
 use termion::{clear, cursor};
 
 use crate::concurrent::atomic_memory::AtomicMemoryReference;
+use crate::logging::ring_logger::LogBuffer;
 use crate::memory::reader::{MemoryReader, MemoryMap};
-use crate::cli::table::render_memory_table;
+use crate::cli::table::{render_memory_table, ViewMode};
+
+/// A registered watchpoint: the last byte observed at `addr`, used to detect
+/// changes across refreshes.
+struct Watchpoint {
+    addr: usize,
+    last: Option<u8>,
+}
 
 pub struct Console {
     pid: i32,
@@ -16,8 +24,15 @@ pub struct Console {
     height: usize,
     convert_ascii: bool,
     memref: AtomicMemoryReference,
+    watchpoints: Vec<Watchpoint>,
+    last_command: String,
+    log_buffer: LogBuffer,
+    show_log: bool,
 }
 
+/// Number of log lines shown in the log panel when it is visible.
+const LOG_PANEL_LINES: usize = 8;
+
 impl Console {
     pub fn new(
         pid: i32,
@@ -26,6 +41,7 @@ impl Console {
         height: usize,
         width: usize,
         convert_ascii: bool,
+        log_buffer: LogBuffer,
     ) -> Self {
         Self {
             pid,
@@ -34,13 +50,120 @@ impl Console {
             height,
             convert_ascii,
             memref,
+            watchpoints: Vec::new(),
+            last_command: String::new(),
+            log_buffer,
+            show_log: false,
+        }
+    }
+
+    /// Render the log panel (most recent entries) at the bottom of the screen.
+    fn render_log_panel(&self, stdout: &mut impl Write, row: u16) {
+        write!(stdout, "{}-- log --", termion::cursor::Goto(1, row)).unwrap();
+        for (i, line) in self.log_buffer.recent(LOG_PANEL_LINES).iter().enumerate() {
+            write!(
+                stdout,
+                "{}{}{}",
+                termion::cursor::Goto(1, row + 1 + i as u16),
+                termion::clear::CurrentLine,
+                line
+            )
+            .unwrap();
+        }
+    }
+
+    /// Read a byte from the current snapshot, or `None` if the address is not
+    /// mapped. Reads go through the shared [`AtomicMemoryReference`].
+    fn read_byte(&self, addr: usize) -> Option<u8> {
+        let (_, meta, bytes, _) = self.memref.get_range(addr, addr + 1);
+        meta.and(bytes.into_iter().next())
+    }
+
+    /// Re-check every watchpoint against the current snapshot, update the stored
+    /// value, and return a status line describing the first change seen (if any).
+    fn check_watchpoints(&mut self) -> Option<String> {
+        let mut flash = None;
+        for wp in self.watchpoints.iter_mut() {
+            let current = self.memref.get_range(wp.addr, wp.addr + 1);
+            let value = current.1.and(current.2.into_iter().next());
+            if wp.last.is_some() && value != wp.last {
+                flash.get_or_insert_with(|| {
+                    format!(
+                        "watch {:#018x}: {:02x?} -> {:02x?}",
+                        wp.addr, wp.last, value
+                    )
+                });
+            }
+            wp.last = value;
+        }
+        flash
+    }
+
+    /// Parse and run a single REPL command. Returns a status line to display.
+    /// Modeled on a debugger's whitespace-separated command dispatch.
+    fn run_command(
+        &mut self,
+        line: &str,
+        selected_region: &mut Option<MemoryMap>,
+        memory_offset: &mut usize,
+    ) -> String {
+        // An empty line repeats the last command (debugger repeat behavior).
+        let line = if line.trim().is_empty() {
+            self.last_command.clone()
+        } else {
+            self.last_command = line.trim().to_string();
+            self.last_command.clone()
+        };
+
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("goto") => match tokens.next().and_then(parse_hex_addr) {
+                Some(addr) => {
+                    let (_, meta, _, _) = self.memref.get_range(addr, addr + 1);
+                    match meta {
+                        Some(map) => {
+                            *memory_offset = addr - map.metadata.range.start;
+                            *selected_region = Some(map);
+                            format!("goto {:#018x}", addr)
+                        }
+                        None => format!("goto: {:#018x} is not mapped", addr),
+                    }
+                }
+                None => "goto: expected a hex address".to_string(),
+            },
+            Some("find") => match parse_hex_bytes(tokens.next().unwrap_or("")) {
+                Some(pattern) if !pattern.is_empty() => match selected_region {
+                    Some(region) => match find_pattern(&region.memory, *memory_offset, &pattern) {
+                        Some(hit) => {
+                            *memory_offset = hit;
+                            format!("find: match at offset +{}", hit)
+                        }
+                        None => "find: pattern not found".to_string(),
+                    },
+                    None => "find: select a region first".to_string(),
+                },
+                _ => "find: expected hex bytes".to_string(),
+            },
+            Some("watch") => match tokens.next().and_then(parse_hex_addr) {
+                Some(addr) => {
+                    let last = self.read_byte(addr);
+                    self.watchpoints.push(Watchpoint { addr, last });
+                    format!("watch {:#018x} registered", addr)
+                }
+                None => "watch: expected a hex address".to_string(),
+            },
+            Some(other) => format!("unknown command: {}", other),
+            None => String::new(),
         }
     }
 
     pub fn start(&mut self) {
         let input = stdin();  // Get user input from stdin
         let mut stdout = stdout().into_raw_mode().unwrap();  // Set terminal to raw mode for real-time input
-        let mut memory_reader = MemoryReader::new(self.pid, true);  // Read memory from the process
+        // The console only needs /proc/<pid>/mem read access; use the syscall
+        // register reader so the panel never ptrace-attaches the inferior (which
+        // would SIGSTOP it on every redraw and fail outright under yama).
+        let mut memory_reader = MemoryReader::new(self.pid, false);  // Read memory from the process
         let mut selected_region: Option<MemoryMap> = None;  // Store the selected memory region
         let mut cursor_position: usize = 0;  // Track the selected region in the list
         let mut memory_offset: usize = 0;  // Offset for memory viewing (scrolling)
@@ -76,8 +199,26 @@ impl Console {
                     .unwrap();
                     num += 1;  // Increment line number for each region
                 }
-                write!(stdout, "{}Press 'q' to quit, or choose a region by number.\n", termion::cursor::Goto(1, num)).unwrap();
+                // Register inspector panel.
+                let regs = memory_reader.get_registers();
+                write!(stdout, "{}Registers:\n", termion::cursor::Goto(1, num)).unwrap();
+                num += 1;
+                write!(stdout, "{} rip={:#018x} rflags={:#018x}\n", termion::cursor::Goto(1, num), regs.rip, regs.rflags).unwrap();
+                num += 1;
+                write!(stdout, "{} rsp={:#018x} rbp={:#018x}\n", termion::cursor::Goto(1, num), regs.rsp, regs.rbp).unwrap();
+                num += 1;
+                write!(stdout, "{} rax={:#018x} rbx={:#018x} rcx={:#018x} rdx={:#018x}\n", termion::cursor::Goto(1, num), regs.rax, regs.rbx, regs.rcx, regs.rdx).unwrap();
                 num += 1;
+                write!(stdout, "{} rsi={:#018x} rdi={:#018x} r8={:#018x} r9={:#018x}\n", termion::cursor::Goto(1, num), regs.rsi, regs.rdi, regs.r8, regs.r9).unwrap();
+                num += 1;
+
+                write!(stdout, "{}Press 'q' to quit, 'l' to toggle the log, or choose a region by number.\n", termion::cursor::Goto(1, num)).unwrap();
+                num += 1;
+
+                if self.show_log {
+                    self.render_log_panel(&mut stdout, num as u16 + 1);
+                    stdout.flush().unwrap();
+                }
             }
 
             // Get user input for navigation
@@ -92,6 +233,10 @@ impl Console {
                             break;
                         }
                     }
+                    Key::Char('l') => {
+                        self.show_log = !self.show_log;  // Toggle the log panel
+                        break;
+                    }
                     Key::Up => {
                         if cursor_position > 0 {
                             cursor_position -= 1;  // Move the cursor up in the region list
@@ -114,7 +259,7 @@ impl Console {
                 }
             }
 
-            if let Some(ref region) = selected_region {
+            if let Some(region) = selected_region.clone() {
                 // Clear the screen before rendering the memory content
                 clear_screen();
                 num = 1;
@@ -130,13 +275,32 @@ impl Console {
                 let memory_len = region.memory.len();
             
                 let start_offset = memory_offset;  // This is where we start in the memory slice.
-                
+
                 // Calculate the end of the range in memory (start + width * height)
                 let end_offset = (start_offset + self.width * self.height).min(memory_len);
-            
-                // Now slice the memory properly based on the `start_offset` and `end_offset`
-                let bytes = &region.memory[start_offset..end_offset];
-            
+
+                // Pull the bytes AND their change bitmap from the same shared
+                // snapshot so the highlight corresponds to the bytes shown. Fall
+                // back to the locally-read region only while the snapshot has
+                // not caught up (nothing highlighted in that case).
+                let abs_start = region.metadata.range.start + start_offset;
+                let abs_end = region.metadata.range.start + end_offset;
+                let (bytes, changed) = match self.memref.get_range(abs_start, abs_end) {
+                    (_, Some(_), snap_bytes, snap_changed) => (snap_bytes, snap_changed),
+                    _ => (
+                        region.memory[start_offset..end_offset].to_vec(),
+                        vec![false; end_offset - start_offset],
+                    ),
+                };
+                let bytes = &bytes[..];
+
+                // Executable regions are far more useful disassembled than as raw hex.
+                let mode = if region.metadata.permissions.contains('x') {
+                    ViewMode::Disassembly
+                } else {
+                    ViewMode::Hex
+                };
+
                 // Render the memory content
                 let table_output = render_memory_table(
                     self.start,
@@ -145,19 +309,89 @@ impl Console {
                     self.height,
                     self.convert_ascii,
                     Some(region.clone()),  // Pass borrowed region reference
+                    &changed,
+                    mode,
                 );
             
                 write!(stdout, "{}", table_output).unwrap();
+
+                // Surface the first watchpoint change seen since the last
+                // refresh on a status line below the table.
+                if let Some(flash) = self.check_watchpoints() {
+                    write!(
+                        stdout,
+                        "{}{}! {}",
+                        termion::cursor::Goto(1, (self.height + 3) as u16),
+                        termion::clear::CurrentLine,
+                        flash
+                    )
+                    .unwrap();
+                }
+
+                if self.show_log {
+                    self.render_log_panel(&mut stdout, (self.height + 5) as u16);
+                }
                 stdout.flush().unwrap();
-            
-                // Process user input for navigation within the selected region
+
+                // Process user input for navigation within the selected region.
+                // Pressing ':' enters command-line mode (goto/find/watch).
+                let mut command_buffer: Option<String> = None;
                 for key in input.lock().keys() {
-                    match key.unwrap() {
+                    let key = key.unwrap();
+
+                    if let Some(buf) = command_buffer.as_mut() {
+                        match key {
+                            Key::Char('\n') => {
+                                let cmd = buf.clone();
+                                let status =
+                                    self.run_command(&cmd, &mut selected_region, &mut memory_offset);
+                                write!(
+                                    stdout,
+                                    "{}{}{}",
+                                    termion::cursor::Goto(1, 1),
+                                    termion::clear::CurrentLine,
+                                    status
+                                )
+                                .unwrap();
+                                stdout.flush().unwrap();
+                                break;
+                            }
+                            Key::Esc => break,
+                            Key::Backspace => {
+                                buf.pop();
+                            }
+                            Key::Char(c) => buf.push(c),
+                            _ => {}
+                        }
+                        if let Some(b) = command_buffer.as_ref() {
+                            write!(
+                                stdout,
+                                "{}{}:{}",
+                                termion::cursor::Goto(1, 1),
+                                termion::clear::CurrentLine,
+                                b
+                            )
+                            .unwrap();
+                            stdout.flush().unwrap();
+                        }
+                        continue;
+                    }
+
+                    match key {
+                        Key::Char(':') => {
+                            command_buffer = Some(String::new());
+                            write!(stdout, "{}{}:", termion::cursor::Goto(1, 1), termion::clear::CurrentLine).unwrap();
+                            stdout.flush().unwrap();
+                        }
                         Key::Char('q') => {
                             selected_region = None;
                             memory_offset = 0;
                             break;
                         }
+                        Key::Char('l') => {
+                            self.show_log = !self.show_log;  // Toggle the log panel
+                            break;
+                        }
                         Key::Up => {
                             if memory_offset > 0 {
                                 memory_offset -= self.width;  // Move the memory window up by one row
@@ -177,6 +411,35 @@ impl Console {
     }
 }
 
+/// Parse an absolute address written in hex, with or without a `0x` prefix.
+fn parse_hex_addr(token: &str) -> Option<usize> {
+    usize::from_str_radix(token.trim_start_matches("0x"), 16).ok()
+}
+
+/// Parse a run of hex bytes (e.g. `deadbeef` or `de ad be ef`) into a pattern.
+fn parse_hex_bytes(token: &str) -> Option<Vec<u8>> {
+    let digits: String = token.chars().filter(|c| !c.is_whitespace()).collect();
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Scan `haystack` from `from` onward for the first occurrence of `pattern`,
+/// returning the byte offset of the match.
+fn find_pattern(haystack: &[u8], from: usize, pattern: &[u8]) -> Option<usize> {
+    if pattern.is_empty() || from >= haystack.len() {
+        return None;
+    }
+    haystack[from..]
+        .windows(pattern.len())
+        .position(|w| w == pattern)
+        .map(|pos| from + pos)
+}
+
 /// Function to clear the screen and move cursor to the top-left corner
 fn clear_screen() {
     let mut stdout = stdout();
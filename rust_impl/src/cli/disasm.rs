@@ -0,0 +1,171 @@
+//! Instruction decoding for executable (`r-x`) regions.
+//!
+//! The renderer walks a byte slice one instruction at a time: it asks the
+//! decoder for the opcode at the cursor, prints it, then advances by the
+//! decoded length — the same opcode→length→operand loop a CPU emulator uses,
+//! driven here by an x86-64 table. The decoder is pluggable via
+//! [`InstructionDecoder`] so other architectures can be slotted in later.
+
+/// A single decoded instruction: how many bytes it occupies and its textual
+/// representation (mnemonic plus operands).
+pub struct DecodedInsn {
+    pub len: usize,
+    pub text: String,
+}
+
+/// Decode the instruction at the start of `bytes`, located at virtual address
+/// `addr`. Implementations must report the instruction's true length, even if
+/// it runs past the end of `bytes`, so the caller can detect a truncated tail.
+pub trait InstructionDecoder {
+    fn decode(&self, bytes: &[u8], addr: usize) -> DecodedInsn;
+}
+
+/// A compact x86-64 decoder. It resolves instruction length for the common
+/// encodings and names the opcodes it recognises; anything else is rendered as
+/// a single `.byte` so the cursor still advances deterministically.
+pub struct X86_64Decoder;
+
+impl InstructionDecoder for X86_64Decoder {
+    fn decode(&self, bytes: &[u8], _addr: usize) -> DecodedInsn {
+        let mut cursor = 0;
+
+        // Skip legacy prefixes (segment/operand/address/lock/rep).
+        while let Some(&b) = bytes.get(cursor) {
+            match b {
+                0x2E | 0x36 | 0x3E | 0x26 | 0x64 | 0x65 | 0x66 | 0x67 | 0xF0 | 0xF2 | 0xF3 => {
+                    cursor += 1;
+                }
+                _ => break,
+            }
+        }
+
+        // Skip a REX prefix (0x40..=0x4F) if present, noting its W bit.
+        let rex = bytes.get(cursor).copied().filter(|b| (0x40..=0x4F).contains(b));
+        if rex.is_some() {
+            cursor += 1;
+        }
+        let rex_w = rex.map_or(false, |b| b & 0x08 != 0);
+
+        let opcode = match bytes.get(cursor) {
+            Some(&b) => b,
+            None => return DecodedInsn { len: bytes.len() + 1, text: "(truncated)".to_string() },
+        };
+
+        // (mnemonic, has_modrm, immediate_bytes) for the opcodes we name.
+        let (mnemonic, has_modrm, imm) = match opcode {
+            0x90 => ("nop", false, 0),
+            0xC3 => ("ret", false, 0),
+            0xCC => ("int3", false, 0),
+            0xC9 => ("leave", false, 0),
+            0xF4 => ("hlt", false, 0),
+            0x50..=0x57 => ("push", false, 0),
+            0x58..=0x5F => ("pop", false, 0),
+            0xE8 => ("call", false, 4),
+            0xE9 => ("jmp", false, 4),
+            0xEB => ("jmp", false, 1),
+            0x70..=0x7F => ("jcc", false, 1),
+            0x6A => ("push", false, 1),
+            0x68 => ("push", false, 4),
+            0xB8..=0xBF => ("mov", false, if rex_w { 8 } else { 4 }),
+            0x88 | 0x8A => ("mov", true, 0),
+            0x89 | 0x8B => ("mov", true, 0),
+            0x8D => ("lea", true, 0),
+            0x01 | 0x03 => ("add", true, 0),
+            0x29 | 0x2B => ("sub", true, 0),
+            0x31 | 0x33 => ("xor", true, 0),
+            0x39 | 0x3B => ("cmp", true, 0),
+            0x85 => ("test", true, 0),
+            0xC7 => ("mov", true, 4),
+            0x83 => ("grp1", true, 1),
+            0x81 => ("grp1", true, 4),
+            _ => {
+                return DecodedInsn {
+                    len: cursor + 1,
+                    text: format!(".byte 0x{:02x}", opcode),
+                }
+            }
+        };
+        cursor += 1;
+
+        // A two-register ModRM plus optional SIB and displacement.
+        if has_modrm {
+            match bytes.get(cursor) {
+                Some(&modrm) => {
+                    cursor += 1;
+                    let md = modrm >> 6;
+                    let rm = modrm & 0x07;
+
+                    // SIB byte follows when mod != 3 and rm == 4.
+                    if md != 3 && rm == 4 {
+                        cursor += 1;
+                    }
+
+                    // Displacement size from the mod field (and the [rip]/disp32 case).
+                    cursor += match md {
+                        0 if rm == 5 => 4,
+                        1 => 1,
+                        2 => 4,
+                        _ => 0,
+                    };
+                }
+                None => return DecodedInsn { len: bytes.len() + 1, text: "(truncated)".to_string() },
+            }
+        }
+
+        cursor += imm;
+
+        DecodedInsn {
+            len: cursor,
+            text: mnemonic.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(bytes: &[u8]) -> DecodedInsn {
+        X86_64Decoder.decode(bytes, 0)
+    }
+
+    #[test]
+    fn single_byte_opcodes() {
+        assert_eq!(decode(&[0x90]).len, 1);
+        assert_eq!(decode(&[0x90]).text, "nop");
+        assert_eq!(decode(&[0xC3]).text, "ret");
+        assert_eq!(decode(&[0x55]).len, 1); // push rbp
+    }
+
+    #[test]
+    fn immediate_operands() {
+        // call rel32: opcode + 4-byte immediate.
+        assert_eq!(decode(&[0xE8, 0, 0, 0, 0]).len, 5);
+        // mov r64, imm64 under REX.W: rex + opcode + 8-byte immediate.
+        assert_eq!(decode(&[0x48, 0xB8, 1, 2, 3, 4, 5, 6, 7, 8]).len, 10);
+    }
+
+    #[test]
+    fn modrm_sib_and_displacement() {
+        assert_eq!(decode(&[0x89, 0xC3]).len, 2); // mov ebx, eax (reg-direct)
+        assert_eq!(decode(&[0x8D, 0x45, 0x10]).len, 3); // lea r, [rbp+disp8]
+        assert_eq!(decode(&[0x8D, 0x05, 0, 0, 0, 0]).len, 6); // lea r, [rip+disp32]
+        assert_eq!(decode(&[0x8B, 0x04, 0x24]).len, 3); // mov r, [rsp] (SIB, no disp)
+    }
+
+    #[test]
+    fn truncated_tail_reports_overrun() {
+        // A ModRM byte is promised but missing: the reported length runs past
+        // the slice so the renderer can flag a truncated tail.
+        let insn = decode(&[0x89]);
+        assert_eq!(insn.text, "(truncated)");
+        assert!(insn.len > 1);
+    }
+
+    #[test]
+    fn unknown_opcode_advances_one_byte() {
+        let insn = decode(&[0x06]);
+        assert_eq!(insn.len, 1);
+        assert!(insn.text.starts_with(".byte"));
+    }
+}
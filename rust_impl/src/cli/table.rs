@@ -1,6 +1,15 @@
 use std::fmt::Write;
+use crate::cli::disasm::{InstructionDecoder, X86_64Decoder};
 use crate::memory::reader::MemoryMap;
 
+/// How a memory window is rendered.
+pub enum ViewMode {
+    /// Classic hex + ASCII grid.
+    Hex,
+    /// Disassembled instructions, for executable (`r-x`) regions.
+    Disassembly,
+}
+
 pub fn render_memory_table(
     start_address: usize,
     bytes: &[u8],
@@ -8,6 +17,8 @@ pub fn render_memory_table(
     height: usize,
     show_ascii: bool,
     meta: Option<MemoryMap>,
+    changed: &[bool],
+    mode: ViewMode,
 ) -> String {
     let mut num = 2;
     let mut output = String::new();
@@ -18,6 +29,10 @@ pub fn render_memory_table(
         None => "No memory region found".to_string(),  // If no MemoryMap, provide a default message
     };
 
+    if let ViewMode::Disassembly = mode {
+        return render_disassembly(start_address, bytes, height, &path);
+    }
+
     for row in 0..height {
         let row_start = row * width;
         if row_start >= bytes.len() {
@@ -29,8 +44,13 @@ pub fn render_memory_table(
 
         write!(output, "{}{:#018x}: ",termion::cursor::Goto(1, num), start_address + row_start).unwrap();
 
-        for byte in slice {
-            write!(output, "{:02x} ", byte).unwrap();
+        for (i, byte) in slice.iter().enumerate() {
+            // Highlight bytes that changed since the previous snapshot in inverse video.
+            if changed.get(row_start + i).copied().unwrap_or(false) {
+                write!(output, "{}{:02x}{} ", termion::style::Invert, byte, termion::style::Reset).unwrap();
+            } else {
+                write!(output, "{:02x} ", byte).unwrap();
+            }
         }
 
         for _ in slice.len()..width {
@@ -55,3 +75,48 @@ pub fn render_memory_table(
     }
     output
 }
+
+/// Render `bytes` as a sequence of instructions, one per row, advancing the
+/// cursor by each decoded length. If the slice ends mid-instruction, a final
+/// `(truncated)` row is emitted rather than over-reading.
+fn render_disassembly(start_address: usize, bytes: &[u8], height: usize, path: &str) -> String {
+    let decoder = X86_64Decoder;
+    let mut output = String::new();
+    let mut num = 2;
+    let mut cursor = 0;
+
+    for _ in 0..height {
+        if cursor >= bytes.len() {
+            break;
+        }
+
+        let insn = decoder.decode(&bytes[cursor..], start_address + cursor);
+
+        // The decoder reports the true length even when it runs past the slice;
+        // treat that as a truncated tail and stop.
+        if insn.len == 0 || cursor + insn.len > bytes.len() {
+            write!(output, "{}{:#018x}: (truncated)\n{}",
+                termion::cursor::Goto(1, num),
+                start_address + cursor,
+                termion::cursor::Goto(1, num + 1)).unwrap();
+            break;
+        }
+
+        let raw: String = bytes[cursor..cursor + insn.len]
+            .iter()
+            .map(|b| format!("{:02x} ", b))
+            .collect();
+
+        write!(output, "{}{:#018x}: {:<24}{}   {}\n{}",
+            termion::cursor::Goto(1, num),
+            start_address + cursor,
+            raw,
+            insn.text,
+            path,
+            termion::cursor::Goto(1, num + 1)).unwrap();
+
+        cursor += insn.len;
+        num += 1;
+    }
+    output
+}
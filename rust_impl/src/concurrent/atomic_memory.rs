@@ -1,60 +1,122 @@
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
 
 use crate::memory::reader::{MemoryMap, AddressRange};
 
 #[derive(Clone)]
 pub struct AtomicMemoryReference {
-    inner: Arc<Mutex<MemoryRefData>>,
+    inner: Arc<ArcSwap<MemoryRefData>>,
 }
 
 struct MemoryRefData {
     maps: HashMap<AddressRange, MemoryMap>,
     ranges: Vec<AddressRange>,
+    // Per range, a bitmap flagging the byte offsets whose value differs from the
+    // previous snapshot. Aligned with the corresponding `MemoryMap::memory`.
+    changed: HashMap<AddressRange, Vec<bool>>,
 }
 
 impl AtomicMemoryReference {
     pub fn new() -> Self {
         Self {
-            inner: Arc::new(Mutex::new(MemoryRefData {
+            inner: Arc::new(ArcSwap::from_pointee(MemoryRefData {
                 maps: HashMap::new(),
                 ranges: Vec::new(),
+                changed: HashMap::new(),
             })),
         }
     }
 
-    pub fn get_range(&self, start: usize, end: usize) -> (usize, Option<MemoryMap>, Vec<u8>) {
-        let data = self.inner.lock().unwrap();
-        let mut result = Vec::new();
-        let mut meta = None;
-        
-        for (i, range) in data.ranges.iter().enumerate() {
-            if let Some(map) = data.maps.get(range) {
-                if range.contains(start) {
-                    meta = Some(map.clone()); // Capture the memory map (including path)
-                    result.extend_from_slice(map.slice(start, end)); // Add memory content
-                    return (i, meta, result); // Return the content and metadata
+    /// Look up the range containing `start` and return its index, metadata, the
+    /// requested bytes, and a `changed` bitmap (aligned with the bytes) flagging
+    /// which of them differ from the previous snapshot.
+    pub fn get_range(&self, start: usize, end: usize) -> (usize, Option<MemoryMap>, Vec<u8>, Vec<bool>) {
+        let data = self.inner.load();
+
+        // `ranges` is kept sorted by start address, so binary-search for the
+        // range that contains `start` in O(log n) instead of scanning linearly.
+        match data.ranges.binary_search_by(|range| {
+            if start < range.start {
+                std::cmp::Ordering::Greater
+            } else if start >= range.end {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        }) {
+            Ok(i) => {
+                let range = &data.ranges[i];
+                match data.maps.get(range) {
+                    Some(map) => {
+                        let bytes = map.slice(start, end).to_vec();
+                        // Align the change bitmap to the same offset/length as the bytes.
+                        let offset = start.saturating_sub(range.start);
+                        let changed = data
+                            .changed
+                            .get(range)
+                            .map(|bits| {
+                                bits.iter()
+                                    .skip(offset)
+                                    .take(bytes.len())
+                                    .copied()
+                                    .collect::<Vec<bool>>()
+                            })
+                            .unwrap_or_else(|| vec![false; bytes.len()]);
+                        (i, Some(map.clone()), bytes, changed)
+                    }
+                    None => (0, None, vec![0; end - start], vec![false; end - start]),
                 }
             }
+            Err(_) => (0, None, vec![0; end - start], vec![false; end - start]), // Fallback
         }
-        
-        (0, meta, vec![0; end - start]) // Fallback if not found
     }
-    
 
     pub fn set_maps(&self, memory_maps: Vec<MemoryMap>) {
-        let mut data = self.inner.lock().unwrap();
-        data.maps.clear();
-        data.ranges.clear();
+        // Build a fresh, immutable snapshot and swap it in with a single store
+        // so readers on the rendering path never block on the writer thread.
+        let prev = self.inner.load();
+
+        let mut maps = HashMap::new();
+        let mut ranges = Vec::new();
+        let mut changed = HashMap::new();
         for map in memory_maps {
             let range = map.metadata.range.clone();
-            data.maps.insert(range.clone(), map);
-            data.ranges.push(range);
+
+            // Diff this range against the same range in the previous snapshot.
+            let mut bits: Vec<bool> = match prev.maps.get(&range) {
+                Some(old) => map
+                    .memory
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| old.memory.get(i) != Some(b))
+                    .collect(),
+                None => vec![false; map.memory.len()], // New range: nothing to compare against.
+            };
+
+            // Fold in the kernel's soft-dirty report: any byte on a page the
+            // target wrote during the interval is flagged changed, so the
+            // renderer highlights writes even where the byte-diff can't see them
+            // (e.g. the first snapshot, or a write that restored the old value).
+            for page in &map.dirty {
+                let lo = page.start.saturating_sub(range.start);
+                let hi = (page.end.saturating_sub(range.start)).min(bits.len());
+                for bit in bits.iter_mut().take(hi).skip(lo) {
+                    *bit = true;
+                }
+            }
+
+            changed.insert(range.clone(), bits);
+            maps.insert(range.clone(), map);
+            ranges.push(range);
         }
-        data.ranges.sort(); // Ensure deterministic order
+        ranges.sort(); // Ensure deterministic order
+
+        self.inner.store(Arc::new(MemoryRefData { maps, ranges, changed }));
     }
 
     pub fn ranges(&self) -> Vec<AddressRange> {
-        self.inner.lock().unwrap().ranges.clone()
+        self.inner.load().ranges.clone()
     }
 }
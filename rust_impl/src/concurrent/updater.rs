@@ -5,6 +5,7 @@ use std::time::Duration;
 
 use crate::concurrent::atomic_memory::AtomicMemoryReference;
 use crate::memory::reader::MemoryReader;
+use crate::memory::registers::Registers;
 
 pub struct MemoryUpdater {
     thread: Option<JoinHandle<()>>,
@@ -30,10 +31,23 @@ impl MemoryUpdater {
 
         running.store(true, Ordering::SeqCst);
         self.thread = Some(thread::spawn(move || {
+            // Read once immediately so the UI has content at startup, then open
+            // the first sampling interval by resetting soft-dirty bits.
+            memory_reference.set_maps(reader.read_memory());
+            let mut soft_dirty = reader.clear_soft_dirty().is_ok();
+
             while running.load(Ordering::SeqCst) {
-                let maps = reader.read_memory();
-                memory_reference.set_maps(maps);
                 thread::sleep(Duration::from_secs(5));
+                // The pages the target touched during the interval come back
+                // tagged on each `MemoryMap`; re-open the next interval after.
+                // Without a successful reset the tags are not per-interval, so
+                // they are dropped and only the byte-level diff applies.
+                let mut maps = reader.read_memory();
+                if !soft_dirty {
+                    maps.iter_mut().for_each(|m| m.dirty.clear());
+                }
+                memory_reference.set_maps(maps);
+                soft_dirty = reader.clear_soft_dirty().is_ok();
             }
         }));
     }
@@ -45,4 +59,8 @@ impl MemoryUpdater {
     pub fn get_stack_pointer(&self) -> usize {
         self.reader.get_stack_pointer()
     }
+
+    pub fn get_registers(&self) -> Registers {
+        self.reader.get_registers()
+    }
 }
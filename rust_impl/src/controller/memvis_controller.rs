@@ -1,6 +1,7 @@
 use crate::cli::console::Console;
 use crate::concurrent::atomic_memory::AtomicMemoryReference;
 use crate::concurrent::updater::MemoryUpdater;
+use crate::logging::ring_logger::LogBuffer;
 
 /// MemvisController manages memory reading and rendering.
 pub struct MemvisController {
@@ -16,6 +17,7 @@ impl MemvisController {
     /// - `start_address`: Optional starting memory address (hex string)
     /// - `use_ptrace`: Whether to use ptrace for stack pointer
     /// - `convert_ascii`: Whether to render memory bytes as ASCII
+    /// - `log_buffer`: Shared ring buffer backing the console's log panel
     pub fn new(
         pid: i32,
         width: usize,
@@ -23,6 +25,7 @@ impl MemvisController {
         start_address: Option<String>,
         use_ptrace: bool,
         convert_ascii: bool,
+        log_buffer: LogBuffer,
     ) -> Self {
         let memory_reference = AtomicMemoryReference::new();
         let memory_updater = MemoryUpdater::new(pid, memory_reference.clone(), use_ptrace);
@@ -41,6 +44,7 @@ impl MemvisController {
             height,
             width,
             convert_ascii,
+            log_buffer,
         );
 
         Self {
@@ -4,11 +4,13 @@ pub mod controller {
 
 pub mod cli {
     pub mod console;
+    pub mod disasm;
     pub mod table;
 }
 
 pub mod memory {
     pub mod reader;
+    pub mod registers;
     pub mod stack_pointer;
 }
 
@@ -16,3 +18,7 @@ pub mod concurrent {
     pub mod atomic_memory;
     pub mod updater;
 }
+
+pub mod logging {
+    pub mod ring_logger;
+}
@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// A bounded, shared ring buffer of formatted log lines. Cloning shares the
+/// underlying storage, so the background updater thread and the console render
+/// loop observe the same history.
+#[derive(Clone)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<VecDeque<String>>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    fn push(&self, line: String) {
+        let mut buf = self.inner.lock().unwrap();
+        if buf.len() == self.capacity {
+            buf.pop_front();
+        }
+        buf.push_back(line);
+    }
+
+    /// Return up to the last `n` entries, oldest first.
+    pub fn recent(&self, n: usize) -> Vec<String> {
+        let buf = self.inner.lock().unwrap();
+        let start = buf.len().saturating_sub(n);
+        buf.iter().skip(start).cloned().collect()
+    }
+}
+
+/// A [`log::Log`] implementation that appends each record to a [`LogBuffer`]
+/// instead of writing to stderr, so diagnostics survive the raw-mode terminal.
+struct RingLogger {
+    buffer: LogBuffer,
+}
+
+impl Log for RingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.buffer
+                .push(format!("[{}] {}", record.level(), record.args()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install a ring-buffer logger holding at most `capacity` entries as the global
+/// logger and return a handle for rendering the log panel.
+pub fn install(capacity: usize) -> LogBuffer {
+    let buffer = LogBuffer::new(capacity);
+    let logger = RingLogger {
+        buffer: buffer.clone(),
+    };
+    // Ignore an error if a logger is already installed (e.g. in tests).
+    let _ = log::set_boxed_logger(Box::new(logger));
+    log::set_max_level(LevelFilter::Info);
+    buffer
+}
@@ -1,6 +1,10 @@
 use clap::Parser;
 
 use rust_memvis::controller::memvis_controller::MemvisController;
+use rust_memvis::logging::ring_logger;
+
+/// Number of log entries retained in the in-memory ring buffer.
+const LOG_CAPACITY: usize = 256;
 
 #[derive(Parser)]
 #[clap(name = "memvis", about = "A memory visualization tool for Linux processes.")]
@@ -31,8 +35,9 @@ struct Args {
 }
 
 fn main() {
-    // Initialize logging
-    env_logger::init();
+    // Install the in-memory ring-buffer logger so diagnostics from the
+    // background updater thread survive the raw-mode terminal.
+    let log_buffer = ring_logger::install(LOG_CAPACITY);
 
     // Parse command-line arguments
     let args = Args::parse();
@@ -45,6 +50,7 @@ fn main() {
         args.start_address,
         !args.no_ptrace,
         !args.print_bytes,
+        log_buffer,
     );
 
     // Start the memory visualization controller
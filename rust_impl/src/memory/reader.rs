@@ -1,6 +1,7 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
 
+use crate::memory::registers::{RegisterReader, PtraceRegisterReader, Registers, SyscallRegisterReader};
 use crate::memory::stack_pointer::{StackPointerReader, PtraceStackPointerReader, SyscallStackPointerReader};
 
 #[derive(Clone, Debug)]
@@ -55,6 +56,11 @@ pub struct MemoryMap {
     pub pid: i32,
     pub metadata: AddressSpaceMetadata,
     pub memory: Vec<u8>,
+    /// Pages of this region written since the previous sampling interval, as
+    /// reported by the kernel's soft-dirty machinery. Empty when no page was
+    /// written or soft-dirty is unavailable (e.g. insufficient privileges); the
+    /// two cases are not distinguished.
+    pub dirty: Vec<AddressRange>,
 }
 
 impl MemoryMap {
@@ -70,6 +76,7 @@ pub struct MemoryReader {
     pid: i32,
     maps: Vec<AddressSpaceMetadata>,
     stack_reader: Box<dyn StackPointerReader>,
+    register_reader: Box<dyn RegisterReader>,
 }
 
 impl MemoryReader {
@@ -80,10 +87,17 @@ impl MemoryReader {
             Box::new(SyscallStackPointerReader {})
         };
 
+        let register_reader: Box<dyn RegisterReader> = if use_ptrace {
+            Box::new(PtraceRegisterReader {})
+        } else {
+            Box::new(SyscallRegisterReader {})
+        };
+
         let mut reader = MemoryReader {
             pid,
             maps: vec![],
             stack_reader,
+            register_reader,
         };
 
         reader.refresh_maps();
@@ -94,20 +108,149 @@ impl MemoryReader {
         self.stack_reader.read(self.pid)
     }
 
+    pub fn get_registers(&self) -> Registers {
+        self.register_reader.read_all(self.pid)
+    }
+
     pub fn read_memory(&mut self) -> Vec<MemoryMap> {
         self.refresh_maps();
-        let mut maps = Vec::new();
-        for meta in &self.maps {
-            if meta.permissions.contains("r") {
-                let memory = self.read_memory_segment(meta);
-                maps.push(MemoryMap {
+        let readable: Vec<AddressSpaceMetadata> = self
+            .maps
+            .iter()
+            .filter(|meta| meta.permissions.contains("r"))
+            .cloned()
+            .collect();
+
+        let buffers = self.read_segments_vectored(&readable);
+        readable
+            .into_iter()
+            .zip(buffers)
+            .map(|(metadata, memory)| {
+                // Tag the pages the target wrote this interval so the renderer
+                // can highlight just those; an unavailable pagemap yields an
+                // empty list, leaving only the byte-level diff to flag changes.
+                let dirty = self.dirty_pages(&metadata).unwrap_or_default();
+                MemoryMap {
                     pid: self.pid,
-                    metadata: meta.clone(),
+                    metadata,
                     memory,
+                    dirty,
+                }
+            })
+            .collect()
+    }
+
+    /// Read every region in `metas` in as few syscalls as possible via
+    /// `process_vm_readv`, which satisfies many ranges with a single copy. Falls
+    /// back to the per-segment `/proc/<pid>/mem` path when the syscall is
+    /// unavailable (`ENOSYS`) or denied (`EPERM`), and for any chunk that comes
+    /// back short (the call stops at the first unreadable range).
+    fn read_segments_vectored(&self, metas: &[AddressSpaceMetadata]) -> Vec<Vec<u8>> {
+        let mut buffers: Vec<Vec<u8>> = metas.iter().map(|m| vec![0u8; m.size]).collect();
+        let iov_max = iov_max();
+
+        for (chunk_index, chunk) in metas.chunks(iov_max).enumerate() {
+            let base = chunk_index * iov_max;
+
+            // Build parallel local (destination) and remote (source) iovecs.
+            let mut local: Vec<libc::iovec> = Vec::with_capacity(chunk.len());
+            let mut remote: Vec<libc::iovec> = Vec::with_capacity(chunk.len());
+            for (i, meta) in chunk.iter().enumerate() {
+                local.push(libc::iovec {
+                    iov_base: buffers[base + i].as_mut_ptr() as *mut libc::c_void,
+                    iov_len: meta.size,
+                });
+                remote.push(libc::iovec {
+                    iov_base: meta.range.start as *mut libc::c_void,
+                    iov_len: meta.size,
                 });
             }
+
+            let transferred = unsafe {
+                libc::process_vm_readv(
+                    self.pid,
+                    local.as_ptr(),
+                    local.len() as libc::c_ulong,
+                    remote.as_ptr(),
+                    remote.len() as libc::c_ulong,
+                    0,
+                )
+            };
+
+            let chunk_total: usize = chunk.iter().map(|m| m.size).sum();
+            if transferred < 0 {
+                match std::io::Error::last_os_error().raw_os_error() {
+                    // Syscall missing or not permitted: give up on the fast path entirely.
+                    Some(libc::ENOSYS) | Some(libc::EPERM) => {
+                        return metas.iter().map(|m| self.read_memory_segment(m)).collect();
+                    }
+                    // Some other failure for this chunk: read its regions one by one.
+                    _ => self.fill_chunk_fallback(chunk, base, &mut buffers),
+                }
+            } else if (transferred as usize) < chunk_total {
+                // Partial read: the call stopped at an unreadable range. Re-read
+                // this chunk's regions individually so the readable ones survive.
+                self.fill_chunk_fallback(chunk, base, &mut buffers);
+            }
+        }
+
+        buffers
+    }
+
+    fn fill_chunk_fallback(
+        &self,
+        chunk: &[AddressSpaceMetadata],
+        base: usize,
+        buffers: &mut [Vec<u8>],
+    ) {
+        for (i, meta) in chunk.iter().enumerate() {
+            buffers[base + i] = self.read_memory_segment(meta);
         }
-        maps
+    }
+
+    /// Reset the soft-dirty bits of every page of the target by writing `"4"` to
+    /// `/proc/<pid>/clear_refs`, marking the start of a sampling interval. This
+    /// needs the same privileges as reading `/proc/<pid>/mem`; callers should
+    /// treat an error as "soft-dirty unavailable" and fall back to full reads.
+    pub fn clear_soft_dirty(&self) -> std::io::Result<()> {
+        let path = format!("/proc/{}/clear_refs", self.pid);
+        let mut file = OpenOptions::new().write(true).open(path)?;
+        file.write_all(b"4")
+    }
+
+    /// Return the pages within `meta` whose soft-dirty bit is set — i.e. the
+    /// pages written since the last [`clear_soft_dirty`](Self::clear_soft_dirty).
+    ///
+    /// Each page's 8-byte little-endian `pagemap` entry lives at offset
+    /// `(addr >> page_shift) * 8`. A page is reported dirty only when it is both
+    /// present (bit 63) and soft-dirty (bit 55); entries with the present bit
+    /// clear are not readable and are treated as not-dirty.
+    pub fn dirty_pages(&self, meta: &AddressSpaceMetadata) -> std::io::Result<Vec<AddressRange>> {
+        let page = page_size();
+        let path = format!("/proc/{}/pagemap", self.pid);
+        let mut file = File::open(path)?;
+
+        let mut dirty = Vec::new();
+        let mut addr = meta.range.start;
+        while addr < meta.range.end {
+            file.seek(SeekFrom::Start((addr / page) as u64 * 8))?;
+            let mut entry = [0u8; 8];
+            if file.read_exact(&mut entry).is_err() {
+                break;
+            }
+            let value = u64::from_le_bytes(entry);
+            let present = (value >> 63) & 1 == 1;
+            let soft_dirty = (value >> 55) & 1 == 1;
+            if present && soft_dirty {
+                dirty.push(AddressRange {
+                    start: addr,
+                    end: (addr + page).min(meta.range.end),
+                });
+            }
+            addr += page;
+        }
+
+        Ok(dirty)
     }
 
     fn refresh_maps(&mut self) {
@@ -125,12 +268,19 @@ impl MemoryReader {
         let mem_path = format!("/proc/{}/mem", self.pid);
         let mut file = match File::open(&mem_path) {
             Ok(f) => f,
-            Err(_) => return vec![0; meta.size],
+            Err(e) => {
+                log::warn!("failed to open {}: {}", mem_path, e);
+                return vec![0; meta.size];
+            }
         };
 
         let mut buffer = vec![0; meta.size];
         if file.seek(SeekFrom::Start(meta.range.start as u64)).is_ok() {
-            if let Err(_) = file.read_exact(&mut buffer) {
+            if let Err(e) = file.read_exact(&mut buffer) {
+                log::warn!(
+                    "failed to read {:#018x}-{:#018x}: {}",
+                    meta.range.start, meta.range.end, e
+                );
                 return vec![0; meta.size];
             }
         }
@@ -139,6 +289,28 @@ impl MemoryReader {
     }
 }
 
+/// The maximum number of `iovec`s a single vectored call accepts, queried from
+/// `sysconf(_SC_IOV_MAX)` with the POSIX minimum of 1024 as a fallback.
+fn iov_max() -> usize {
+    let value = unsafe { libc::sysconf(libc::_SC_IOV_MAX) };
+    if value > 0 {
+        value as usize
+    } else {
+        1024
+    }
+}
+
+/// The kernel page size, queried from `sysconf(_SC_PAGESIZE)` rather than
+/// assuming 4096.
+fn page_size() -> usize {
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size > 0 {
+        size as usize
+    } else {
+        4096
+    }
+}
+
 fn parse_maps_line(line: &str) -> Option<AddressSpaceMetadata> {
     let parts: Vec<&str> = line.split_whitespace().collect();
     if parts.len() < 5 {
@@ -0,0 +1,159 @@
+use nix::sys::ptrace;
+use nix::sys::wait::waitpid;
+use nix::unistd::Pid;
+use std::fs::File;
+use std::io::Read;
+
+use libc::{user_regs_struct, c_long};
+
+/// A snapshot of a process's general-purpose registers, as returned by
+/// `PTRACE_GETREGS`. Fields mirror `user_regs_struct`.
+#[derive(Clone, Debug, Default)]
+pub struct Registers {
+    pub rax: u64,
+    pub rbx: u64,
+    pub rcx: u64,
+    pub rdx: u64,
+    pub rsi: u64,
+    pub rdi: u64,
+    pub rbp: u64,
+    pub rsp: u64,
+    pub r8: u64,
+    pub r9: u64,
+    pub r10: u64,
+    pub r11: u64,
+    pub r12: u64,
+    pub r13: u64,
+    pub r14: u64,
+    pub r15: u64,
+    pub rip: u64,
+    pub rflags: u64,
+    pub cs: u64,
+    pub ss: u64,
+    pub ds: u64,
+    pub es: u64,
+    pub fs: u64,
+    pub gs: u64,
+}
+
+pub trait RegisterReader: Send + Sync {
+    fn read_all(&self, pid: i32) -> Registers;
+    fn box_clone(&self) -> Box<dyn RegisterReader>;
+}
+
+impl Clone for Box<dyn RegisterReader> {
+    fn clone(&self) -> Box<dyn RegisterReader> {
+        self.box_clone()
+    }
+}
+
+#[derive(Clone)]
+pub struct PtraceRegisterReader;
+
+impl RegisterReader for PtraceRegisterReader {
+    /// Attach, read the full register file via `PTRACE_GETREGS`, and detach.
+    /// Attaching can fail for reasons entirely outside our control — yama
+    /// `ptrace_scope=1` when the target is not our child, a target that is
+    /// already stopped or has exited, or insufficient privilege — so on any
+    /// such failure fall back to the registers `/proc/<pid>/syscall` still
+    /// exposes rather than panicking and tearing down the raw-mode TUI.
+    fn read_all(&self, pid: i32) -> Registers {
+        match Self::try_read(pid) {
+            Some(regs) => regs,
+            None => SyscallRegisterReader.read_all(pid),
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn RegisterReader> {
+        Box::new(Self {})
+    }
+}
+
+impl PtraceRegisterReader {
+    /// Try the ptrace path, returning `None` on any attach/GETREGS error so the
+    /// caller can fall back. The inferior is always detached before returning.
+    fn try_read(pid: i32) -> Option<Registers> {
+        let target = Pid::from_raw(pid);
+
+        ptrace::attach(target).ok()?;
+        if waitpid(target, None).is_err() {
+            let _ = ptrace::detach(target, None);
+            return None;
+        }
+
+        let regs: user_regs_struct = unsafe {
+            let mut regs: user_regs_struct = std::mem::zeroed();
+            let result = libc::ptrace(libc::PTRACE_GETREGS, pid, std::ptr::null_mut::<c_long>(), &mut regs);
+            if result == -1 {
+                let _ = ptrace::detach(target, None);
+                return None;
+            }
+            regs
+        };
+
+        let _ = ptrace::detach(target, None);
+
+        Some(Registers {
+            rax: regs.rax,
+            rbx: regs.rbx,
+            rcx: regs.rcx,
+            rdx: regs.rdx,
+            rsi: regs.rsi,
+            rdi: regs.rdi,
+            rbp: regs.rbp,
+            rsp: regs.rsp,
+            r8: regs.r8,
+            r9: regs.r9,
+            r10: regs.r10,
+            r11: regs.r11,
+            r12: regs.r12,
+            r13: regs.r13,
+            r14: regs.r14,
+            r15: regs.r15,
+            rip: regs.rip,
+            rflags: regs.eflags,
+            cs: regs.cs,
+            ss: regs.ss,
+            ds: regs.ds,
+            es: regs.es,
+            fs: regs.fs,
+            gs: regs.gs,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct SyscallRegisterReader;
+
+impl RegisterReader for SyscallRegisterReader {
+    /// `/proc/[pid]/syscall` exposes only the stack pointer and program counter
+    /// (the final two hex tokens). The rest of the register file is left zeroed,
+    /// and all of it is zero when the file cannot be read.
+    fn read_all(&self, pid: i32) -> Registers {
+        let path = format!("/proc/{}/syscall", pid);
+        let mut contents = String::new();
+        if File::open(&path)
+            .and_then(|mut file| file.read_to_string(&mut contents))
+            .is_err()
+        {
+            return Registers::default();
+        }
+
+        let hex: Vec<u64> = contents
+            .split_whitespace()
+            .filter_map(|s| s.strip_prefix("0x"))
+            .filter_map(|s| u64::from_str_radix(s, 16).ok())
+            .collect();
+
+        let mut regs = Registers::default();
+        if hex.len() >= 2 {
+            regs.rsp = hex[hex.len() - 2];
+            regs.rip = hex[hex.len() - 1];
+        }
+        regs
+    }
+
+    fn box_clone(&self) -> Box<dyn RegisterReader> {
+        Box::new(Self {})
+    }
+}